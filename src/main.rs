@@ -23,11 +23,23 @@ use crate::AnswerResult::*;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 use actix_web::{get, post, delete, web, App, HttpRequest, HttpResponse, HttpServer, Responder, web::Redirect};
+use actix_web::cookie::{Cookie, SameSite};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use rand::RngCore;
+use actix::{Actor, StreamHandler, AsyncContext};
+use actix_web_actors::ws;
 use base64::{Engine as _, engine::general_purpose::STANDARD_NO_PAD as BASE64};
-use moka::future::Cache;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use async_compression::tokio::write::{GzipEncoder, GzipDecoder};
+use tokio::io::AsyncWriteExt;
 use regex::Regex;
+use qrcode::QrCode;
+use qrcode::render::svg;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
 
 const SERVER_ADDRESS: &str = "0.0.0.0";
 const SERVER_PORT: u16 = 4242;
@@ -80,6 +92,24 @@ const SITE_HEADER: &str = "
 const SITE_FOOTER: &str = "	</body>
 </html>";
 
+// Injected into the admin page at the `BUZZFEED` placeholder (alongside the CAT/P/QRCODE
+// replacements): a WebSocket client that subscribes to /ws and lists buzzes in arrival
+// order, pushing live buzz events to the admin view.
+const ADMIN_BUZZ_FEED: &str = "<ol id=\"buzzfeed\"></ol>
+<script>
+	const feed = document.getElementById(\"buzzfeed\");
+	const ws = new WebSocket((location.protocol === \"https:\" ? \"wss://\" : \"ws://\") + location.host + \"/ws\");
+	ws.onmessage = (e) => {
+		const parts = e.data.split(\"\\t\");
+		if (parts[0] === \"armed\" || parts[0] === \"cleared\") { feed.innerHTML = \"\"; return; }
+		const li = document.createElement(\"li\");
+		if (parts[0] === \"first\") li.textContent = parts[2] + \" (first)\";
+		else if (parts[0] === \"buzz\") li.textContent = parts[2] + \" (+\" + parts[3] + \"ms)\";
+		else return;
+		feed.appendChild(li);
+	};
+</script>";
+
 lazy_static! {
 	static ref IS_VALID_NAME: Regex = Regex::new("^[0-9a-zA-Z_-]+$").unwrap();
 
@@ -103,34 +133,53 @@ struct AdminQuery {
 	setstate: Option<u8>, // set state: Registration or BuzzerActive
 	reset: Option<bool>, // reset entire game, kicking all players
 	player: Option<u8>, // select a player that shall be active now
+	arm: Option<bool>, // arm the buzzer, clearing previous buzzes
+	lock: Option<bool>, // lock the buzzer so no further buzz can take the turn
+	clear: Option<bool>, // disarm and clear the buzzer
+	export: Option<bool>, // download the current game as a compressed archive
+	import: Option<bool>, // resume from the last persisted snapshot
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 struct Answers {
 	categories: Vec<Category>,
-	#[serde(skip)]
-	active_player: Option<u8>,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 struct Category {
 	name: String,
 	answers: Vec<Answer>,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 struct Answer {
 	task: Task,
 	points: u16,
 	double: bool,
-	#[serde(skip)]
+	#[serde(default)]
 	tries: Option<Vec<Try>>,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 enum Task {
-	Picture(String),
 	Text(String),
+	Image(Media),
+	Audio(Media),
+	Video(Media),
+	Picture(String), // legacy: an external image URL, kept so pre-media data files still load
+}
+
+// A media payload for an answer: either referenced by an external URL, or carried inline
+// as base64-encoded content tagged with its MIME kind (decoded with the BASE64 engine).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+enum Media {
+	Url(String),
+	Inline { kind: MediaKind, content: String },
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+struct MediaKind {
+	mime: String,
 }
 
 #[allow(non_camel_case_types)] // this is parsed from query string, which is lowercase by default
@@ -147,7 +196,7 @@ struct Board {
 	players: Vec<Player>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 struct Player {
 	name: String,
 	points: i32,
@@ -158,13 +207,81 @@ struct ActivePlayer {
 	id: u8,
 }
 
-#[derive(Clone, Debug)]
+// LAN-visible host:port the registration QR code should encode. Since SERVER_ADDRESS is
+// 0.0.0.0 (not scannable), this is taken from a CLI argument so phones on the same
+// network reach the right address.
+#[derive(Clone)]
+struct PublicAddress(String);
+
+// Secret used to sign session tokens. Generated fresh on a clean start, but carried in
+// the game snapshot and restored on resume so that players' existing `session` cookies
+// keep verifying after a `--load` or admin import. Shared behind a lock so an import can
+// swap it in for every worker at once.
+#[derive(Clone)]
+struct ServerSecret(Arc<RwLock<Vec<u8>>>);
+
+impl ServerSecret {
+	fn new(bytes: Vec<u8>) -> Self {
+		ServerSecret(Arc::new(RwLock::new(bytes)))
+	}
+
+	fn bytes(&self) -> Vec<u8> {
+		self.0.read().unwrap().clone()
+	}
+
+	fn set(&self, bytes: Vec<u8>) {
+		*self.0.write().unwrap() = bytes;
+	}
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+const TOKEN_NONCE_LEN: usize = 8; // random bytes folded into every token so it is unguessable
+
+// Mint a random signed session token for a player index. The signed payload is the id
+// plus a fresh random nonce, so two tokens for the same id still differ and the cookie is
+// not a fixed function of a sequential id: base64(id || nonce || HMAC(id || nonce)).
+fn sign_token(secret: &[u8], id: u8) -> String {
+	let mut payload = Vec::with_capacity(1 + TOKEN_NONCE_LEN);
+	payload.push(id);
+	let mut nonce = [0u8; TOKEN_NONCE_LEN];
+	rand::thread_rng().fill_bytes(&mut nonce);
+	payload.extend_from_slice(&nonce);
+	let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+	mac.update(&payload);
+	let tag = mac.finalize().into_bytes();
+	let mut raw = payload;
+	raw.extend_from_slice(&tag);
+	BASE64.encode(raw)
+}
+
+// Recover the player index from a token, returning None unless the HMAC checks out.
+fn verify_token(secret: &[u8], token: &str) -> Option<u8> {
+	let raw = BASE64.decode(token).ok()?;
+	if raw.len() <= 1 + TOKEN_NONCE_LEN { return None; }
+	let (payload, tag) = raw.split_at(1 + TOKEN_NONCE_LEN);
+	let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+	mac.update(payload);
+	mac.verify_slice(tag).ok()?;
+	Some(payload[0])
+}
+
+// Resolve the verified player index behind a request from its signed `session` cookie.
+// The HMAC is validated before the id is trusted, so a forged cookie resolves to nothing.
+// The id is returned directly (not a name) so distinct players never collapse together.
+fn resolve_player(req: &HttpRequest, server_secret: &ServerSecret) -> Option<u8> {
+	let cookie = req.cookie("session")?;
+	verify_token(&server_secret.bytes(), cookie.value())
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
 struct Try {
 	player: String,
 	try_result: AnswerResult,
 }
 
-#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)] // kept consistent with the Rating variants parsed from query strings
+#[derive(Clone, Serialize, Deserialize, Debug)]
 enum AnswerResult {
 	positive(u16),
 	negative(u16),
@@ -176,38 +293,191 @@ enum Status {
 	BuzzerActive,
 }
 
+// A single buzz, recorded in arrival order with the instant it reached the server.
+struct Buzz {
+	player: String,
+	at: Instant,
+}
+
+// Shared buzzer state. Only the first buzz while `armed` takes the turn and flips
+// `locked`; every later buzz is still recorded (with its delta) but cannot steal it.
+#[derive(Default)]
+struct BuzzerState {
+	armed: bool,
+	locked: bool,
+	buzzes: Vec<Buzz>,
+}
+
+// WebSocket session pushing buzzer events to an admin or player page. Buzz events are
+// fanned out over a broadcast channel; this actor forwards them to its own socket.
+struct BuzzerSocket {
+	rx: Option<broadcast::Receiver<String>>,
+}
+
+impl Actor for BuzzerSocket {
+	type Context = ws::WebsocketContext<Self>;
+
+	fn started(&mut self, ctx: &mut Self::Context) {
+		if let Some(rx) = self.rx.take() {
+			ctx.add_stream(BroadcastStream::new(rx));
+		}
+	}
+}
+
+// Client frames. We only need to answer pings to keep the socket alive.
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for BuzzerSocket {
+	fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+		match msg {
+			Ok(ws::Message::Ping(m)) => ctx.pong(&m),
+			Ok(ws::Message::Close(reason)) => ctx.close(reason),
+			_ => {}
+		}
+	}
+}
+
+// Broadcasted buzzer events forwarded verbatim to the socket.
+impl StreamHandler<Result<String, tokio_stream::wrappers::errors::BroadcastStreamRecvError>> for BuzzerSocket {
+	fn handle(&mut self, msg: Result<String, tokio_stream::wrappers::errors::BroadcastStreamRecvError>, ctx: &mut Self::Context) {
+		if let Ok(event) = msg {
+			ctx.text(event);
+		}
+	}
+}
+
+// Build the registration URL for a given public address and render it as an inline SVG
+// QR code that can be spliced straight into a page.
+fn qr_svg(public_address: &str) -> String {
+	let url = format!("http://{}/", public_address);
+	let code = QrCode::new(url.as_bytes()).unwrap();
+	code.render::<svg::Color>()
+		.min_dimensions(200, 200)
+		.dark_color(svg::Color("#000000"))
+		.light_color(svg::Color("#ffffff"))
+		.build()
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-	let args: Vec<String> = std::env::args().collect();
 	let pwd = std::env::current_dir()?;
-	let mut path = pwd.clone();
-	if args.len() < 2 { panic!("Provide a file to read the questions from!"); }
-	path.push(&args[1]);
-	
-	let answers_file = fs::read_to_string(path.clone());
-	if answers_file.is_err() { panic!("Could not parse data file!"); }
-	
-	let mut answers: Arc<RwLock<Answers>> = Arc::new(RwLock::new(serde_json::from_str(&answers_file.unwrap()).expect("Data file structure invalid!")));
-	//answers.categories[0].answers[1].tries = Some(vec![Try {player: "bad".to_string(), try_result: AnswerResult::negative(100)}, Try {player: "42".to_string(), try_result: AnswerResult::positive(100)}]);
-	//answers.categories[0].answers[1].tries.as_mut().unwrap().push(Try {player: "42".to_string(), try_result: AnswerResult::positive(10)});
-	
-	let status = Arc::new(RwLock::new(Status::Registration));
-	let ip_cache = Cache::<String, String>::builder().build();
-	let players = Arc::new(RwLock::new(Vec::<Player>::new()));
-	let active_player = Arc::new(RwLock::new(ActivePlayer{id: 0}));
+
+	// Parse `--load` and `--public-address` as named flags, filtering them out before the
+	// positional arguments so they never collide with the question-file / address slots.
+	let mut load_arg: Option<String> = None;
+	let mut public_address_arg: Option<String> = None;
+	let mut positionals: Vec<String> = Vec::new();
+	let mut rest = std::env::args().skip(1);
+	while let Some(arg) = rest.next() {
+		match arg.as_str() {
+			"--load" => load_arg = Some(rest.next().expect("Provide a snapshot file after --load!")),
+			"--public-address" => public_address_arg = Some(rest.next().expect("Provide an address after --public-address!")),
+			_ => positionals.push(arg),
+		}
+	}
+
+	// The LAN-visible host:port to encode in the join QR code: the named flag wins, then a
+	// second positional argument, otherwise a local default. SERVER_ADDRESS is 0.0.0.0 and
+	// not scannable, so this is what phones on the same network actually reach.
+	let public_address = PublicAddress(
+		public_address_arg
+			.or_else(|| positionals.get(1).cloned())
+			.unwrap_or_else(|| format!("localhost:{}", SERVER_PORT))
+	);
+
+	// `--load <snapshot>` resumes a previously persisted game instead of starting fresh
+	// from the question file; a `.gz` snapshot is transparently decompressed.
+	let loaded = match &load_arg {
+		Some(snapshot_arg) => {
+			let mut snapshot_path = pwd.clone();
+			snapshot_path.push(snapshot_arg);
+			if snapshot_arg.ends_with(".gz") {
+				let raw = fs::read(&snapshot_path).expect("Could not read snapshot archive!");
+				let json = gunzip(&raw).await.expect("Could not decompress snapshot archive!");
+				Some(serde_json::from_slice::<Snapshot>(&json).expect("Snapshot archive structure invalid!"))
+			}
+			else {
+				Some(load_snapshot(&snapshot_path).expect("Could not load snapshot!"))
+			}
+		}
+		None => None
+	};
+
+	let answers: Arc<RwLock<Answers>>;
+	let status;
+	let players;
+	let active_player;
+	let server_secret;
+	match loaded {
+		Some(snapshot) => {
+			answers = Arc::new(RwLock::new(snapshot.answers));
+			status = Arc::new(RwLock::new(match snapshot.status { 0 => Status::Registration, _ => Status::BuzzerActive }));
+			players = Arc::new(RwLock::new(snapshot.players));
+			active_player = Arc::new(RwLock::new(ActivePlayer { id: snapshot.active_player }));
+			// Reuse the signing secret from the snapshot so existing cookies stay valid.
+			server_secret = ServerSecret::new(snapshot.secret);
+		}
+		None => {
+			let question_file = positionals.first().expect("Provide a file to read the questions from!");
+			let mut path = pwd.clone();
+			path.push(question_file);
+			let answers_file = match fs::read_to_string(&path) {
+				Ok(contents) => contents,
+				Err(e) => panic!("Could not read data file {}: {}", path.display(), e)
+			};
+			let parsed = match serde_json::from_str(&answers_file) {
+				Ok(parsed) => parsed,
+				// The Task schema gained media variants; call out the change so an old file
+				// with a mistyped/unknown clue kind gets an actionable hint, not just "invalid".
+				Err(e) => panic!("Data file structure invalid ({}). Clue kinds are Text, Image, Audio, Video (each Url or Inline) or the legacy Picture; check the \"task\" fields.", e)
+			};
+			answers = Arc::new(RwLock::new(parsed));
+			status = Arc::new(RwLock::new(Status::Registration));
+			players = Arc::new(RwLock::new(Vec::<Player>::new()));
+			active_player = Arc::new(RwLock::new(ActivePlayer{id: 0}));
+			let mut secret = vec![0u8; 32];
+			rand::thread_rng().fill_bytes(&mut secret);
+			server_secret = ServerSecret::new(secret);
+		}
+	}
+	let buzzer_state = Arc::new(RwLock::new(BuzzerState::default()));
+	let (buzzer_tx, _) = broadcast::channel::<String>(64);
+
+	// Periodically snapshot the full game so a crash never costs more than one interval.
+	{
+		let pwd = pwd.clone();
+		let answers = answers.clone();
+		let players = players.clone();
+		let active_player = active_player.clone();
+		let status = status.clone();
+		let server_secret = server_secret.clone();
+		actix_web::rt::spawn(async move {
+			let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+			loop {
+				interval.tick().await;
+				let status_code = match *status.read().unwrap() { Status::Registration => 0, Status::BuzzerActive => 1 };
+				let active = active_player.read().unwrap().id;
+				save_snapshot(&pwd, &answers.read().unwrap(), &players.read().unwrap(), active, status_code, &server_secret.bytes());
+			}
+		});
+	}
 	HttpServer::new(move || {
 		App::new()
 			.app_data(web::Data::new(status.clone()))
-			.app_data(web::Data::new(ip_cache.clone()))
+			.app_data(web::Data::new(server_secret.clone()))
 			.app_data(web::Data::new(pwd.clone()))
 			.app_data(web::Data::new(answers.clone()))
 			.app_data(web::Data::new(players.clone()))
 			.app_data(web::Data::new(active_player.clone()))
+			.app_data(web::Data::new(buzzer_state.clone()))
+			.app_data(web::Data::new(buzzer_tx.clone()))
+			.app_data(web::Data::new(public_address.clone()))
 			.service(register)
 			.service(buzz)
 			.service(admin)
 			.service(splash)
 			.service(buzzer)
+			.service(buzzer_ws)
+			.service(qr)
+			.service(media)
 			.service(get_answer)
 	})
 	.bind((SERVER_ADDRESS, SERVER_PORT))?
@@ -216,7 +486,7 @@ async fn main() -> std::io::Result<()> {
 }
 
 #[get("/register")]
-async fn register(req: HttpRequest, query: web::Query<RegisterQuery>, status: web::Data<Arc<RwLock<Status>>>, ip_cache: web::Data<Cache<String, String>>, players: web::Data<Arc<RwLock<Vec<Player>>>>) -> impl Responder {
+async fn register(req: HttpRequest, query: web::Query<RegisterQuery>, status: web::Data<Arc<RwLock<Status>>>, server_secret: web::Data<ServerSecret>, players: web::Data<Arc<RwLock<Vec<Player>>>>) -> impl Responder {
 	match *(status.read().unwrap()) {
 		Status::Registration => {},
 		_ => {
@@ -230,33 +500,183 @@ async fn register(req: HttpRequest, query: web::Query<RegisterQuery>, status: we
 		Some(res) => format!("{}", res.ip()),
 		None => return HttpResponse::InternalServerError().body("Could not get IP address".as_bytes())
 	};
-	ip_cache.insert(ip.clone(), query.name.clone()).await;
-	let mut players = players.write().unwrap();
-	players.push(Player {
-		name: query.name.to_string(),
-		points: 0
-	});
-	
+	let id = {
+		let mut players = players.write().unwrap();
+		players.push(Player {
+			name: query.name.to_string(),
+			points: 0
+		});
+		(players.len() - 1) as u8
+	};
+	let token = sign_token(&server_secret.bytes(), id);
+
 	println!("{} registered using name \"{}\"", ip, query.name);
-	HttpResponse::TemporaryRedirect().insert_header(("location", "/buzzer")).finish()
+	HttpResponse::TemporaryRedirect()
+		.insert_header(("location", "/buzzer"))
+		.cookie(Cookie::build("session", token).path("/").http_only(true).same_site(SameSite::Lax).finish())
+		.finish()
 }
 
 #[get("/buzz")]
-async fn buzz(req: HttpRequest, ip_cache: web::Data<Cache<String, String>>) -> impl Responder {
-	let ip: String = match req.peer_addr() {
-		Some(res) => format!("{}", res.ip()),
+async fn buzz(req: HttpRequest, server_secret: web::Data<ServerSecret>, status: web::Data<Arc<RwLock<Status>>>, buzzer_state: web::Data<Arc<RwLock<BuzzerState>>>, players: web::Data<Arc<RwLock<Vec<Player>>>>, active_player: web::Data<Arc<RwLock<ActivePlayer>>>, buzzer_tx: web::Data<broadcast::Sender<String>>) -> impl Responder {
+	let id = match resolve_player(&req, &server_secret) {
+		Some(id) => id,
+		None => return HttpResponse::BadRequest().body("Not registered".as_bytes())
+	};
+	let name = match players.read().unwrap().get(id as usize) {
+		Some(player) => player.name.clone(),
+		None => return HttpResponse::BadRequest().body("Not registered".as_bytes())
+	};
+	match *(status.read().unwrap()) {
+		Status::BuzzerActive => {},
+		_ => return HttpResponse::BadRequest().body("Buzzer is not active".as_bytes())
+	};
+	let mut buzzer_state = buzzer_state.write().unwrap();
+	if !buzzer_state.armed {
+		return HttpResponse::Ok().body("not armed".as_bytes());
+	}
+	let now = Instant::now();
+	let first = !buzzer_state.locked && buzzer_state.buzzes.is_empty();
+	let delta = buzzer_state.buzzes.first().map(|b| now.duration_since(b.at).as_millis()).unwrap_or(0);
+	buzzer_state.buzzes.push(Buzz { player: name.clone(), at: now });
+	if first {
+		buzzer_state.locked = true;
+		*active_player.write().unwrap() = ActivePlayer { id };
+		println!("{} buzzered first!", name);
+		// Broadcast the verified id (for per-client comparison) plus the name (for display).
+		let _ = buzzer_tx.send(format!("first\t{}\t{}", id, name));
+		HttpResponse::Ok().body("you buzzed first".as_bytes())
+	} else {
+		println!("{} buzzered (+{}ms, locked out)", name, delta);
+		let _ = buzzer_tx.send(format!("buzz\t{}\t{}\t{}", id, name, delta));
+		HttpResponse::Ok().body("locked out".as_bytes())
+	}
+}
+
+#[get("/ws")]
+async fn buzzer_ws(req: HttpRequest, stream: web::Payload, buzzer_tx: web::Data<broadcast::Sender<String>>) -> Result<HttpResponse, actix_web::Error> {
+	// Only genuine WebSocket upgrades get spliced onto the actor; everything else is
+	// kept off the normal header path and rejected outright.
+	let is_upgrade = req.headers().get("connection").and_then(|v| v.to_str().ok()).map(|v| v.to_ascii_lowercase().contains("upgrade")).unwrap_or(false);
+	let is_websocket = req.headers().get("upgrade").and_then(|v| v.to_str().ok()).map(|v| v.eq_ignore_ascii_case("websocket")).unwrap_or(false);
+	if !(is_upgrade && is_websocket) {
+		return Ok(HttpResponse::BadRequest().body("Expected a WebSocket upgrade"));
+	}
+	ws::start(BuzzerSocket { rx: Some(buzzer_tx.subscribe()) }, &req, stream)
+}
+
+const SNAPSHOT_FILE: &str = "openjeopardy_snapshot.json";
+
+// A full serializable picture of the game: the board (including recorded tries), the
+// players with their points, the active player, the current status and the signing
+// secret. Written out periodically and on every scoring action so an interrupted game can
+// be resumed — including the players' sessions, whose cookies still verify under the
+// restored secret.
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+	answers: Answers,
+	players: Vec<Player>,
+	active_player: u8,
+	status: u8, // 0 = Registration, 1 = BuzzerActive
+	secret: Vec<u8>,
+}
+
+fn snapshot_path(pwd: &PathBuf) -> PathBuf {
+	let mut path = pwd.clone();
+	path.push(SNAPSHOT_FILE);
+	path
+}
+
+// Serialize the current game to the on-disk snapshot. Best-effort: a failed write is
+// logged but never aborts the request that triggered it.
+fn save_snapshot(pwd: &PathBuf, answers: &Answers, players: &[Player], active_player: u8, status: u8, secret: &[u8]) {
+	let snapshot = Snapshot {
+		answers: answers.clone(),
+		players: players.to_vec(),
+		active_player,
+		status,
+		secret: secret.to_vec(),
+	};
+	match serde_json::to_string(&snapshot) {
+		Ok(json) => {
+			if let Err(e) = fs::write(snapshot_path(pwd), json) {
+				eprintln!("Could not write snapshot: {}", e);
+			}
+		}
+		Err(e) => eprintln!("Could not serialize snapshot: {}", e),
+	}
+}
+
+fn load_snapshot(path: &PathBuf) -> std::io::Result<Snapshot> {
+	let raw = fs::read_to_string(path)?;
+	serde_json::from_str(&raw).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+// Gzip-compress a byte slice using async-compression, for the exported game archive.
+async fn gzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+	let mut encoder = GzipEncoder::new(Vec::new());
+	encoder.write_all(data).await?;
+	encoder.shutdown().await?;
+	Ok(encoder.into_inner())
+}
+
+// Inverse of gzip(), used to read back a compressed archive on import.
+async fn gunzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+	let mut decoder = GzipDecoder::new(Vec::new());
+	decoder.write_all(data).await?;
+	decoder.shutdown().await?;
+	Ok(decoder.into_inner())
+}
+
+// Build the HTML fragment shown on the answer screen for a task. Inline media is served
+// through the /media route; external media is referenced by its URL directly.
+fn render_task(task: &Task, c: u8, a: u8) -> String {
+	let media_src = |media: &Media| match media {
+		Media::Url(url) => url.clone(),
+		Media::Inline { .. } => format!("/media/{}/{}", c, a),
+	};
+	match task {
+		Task::Text(text) => text.clone(),
+		Task::Image(media) => format!("<img src=\"{}\">", media_src(media)),
+		Task::Audio(media) => format!("<audio controls src=\"{}\"></audio>", media_src(media)),
+		Task::Video(media) => format!("<video controls src=\"{}\"></video>", media_src(media)),
+		Task::Picture(link) => format!("<img src=\"{}\">", link),
+	}
+}
+
+#[get("/media/{cat}/{answer}")]
+async fn media(req: HttpRequest, path: web::Path<(u8, u8)>, answers: web::Data<Arc<RwLock<Answers>>>) -> impl Responder {
+	let ip = match req.peer_addr() {
+		Some(res) => res.ip(),
 		None => return HttpResponse::InternalServerError().body("Could not get IP address".as_bytes())
 	};
-	let name = ip_cache.get(&ip).await;
-	if name.is_none() {
-		return HttpResponse::BadRequest().body("Not registered".as_bytes());
+	if !ip.is_loopback() {
+		return HttpResponse::Unauthorized().body("Not an admin".as_bytes());
+	}
+	let (c, a) = path.into_inner();
+	let answers = answers.read().unwrap();
+	let answer = match answers.categories.get(c as usize).and_then(|cat| cat.answers.get(a as usize)) {
+		Some(answer) => answer,
+		None => return HttpResponse::NotFound().body("No such answer".as_bytes())
+	};
+	let media = match &answer.task {
+		Task::Image(media) | Task::Audio(media) | Task::Video(media) => media,
+		// Text has nothing to serve; legacy Picture is referenced by its URL directly.
+		Task::Text(_) | Task::Picture(_) => return HttpResponse::NotFound().body("Answer carries no inline media".as_bytes())
+	};
+	match media {
+		Media::Inline { kind, content } => {
+			match BASE64.decode(content) {
+				Ok(bytes) => HttpResponse::Ok().content_type(kind.mime.clone()).body(bytes),
+				Err(_) => HttpResponse::InternalServerError().body("Could not decode inline media".as_bytes())
+			}
+		}
+		Media::Url(url) => HttpResponse::TemporaryRedirect().insert_header(("location", url.clone())).finish()
 	}
-	println!("{} buzzered!", name.unwrap());
-	HttpResponse::TemporaryRedirect().insert_header(("location", "/buzzer")).finish()
 }
 
 #[get("/answer")]
-async fn get_answer(req: HttpRequest, query: web::Query<AnswerQuery>, pwd: web::Data<PathBuf>, answers: web::Data<Arc<RwLock<Answers>>>, players: web::Data<Arc<RwLock<Vec<Player>>>>, active_player: web::Data<Arc<RwLock<ActivePlayer>>>) -> impl Responder {
+async fn get_answer(req: HttpRequest, query: web::Query<AnswerQuery>, pwd: web::Data<PathBuf>, answers: web::Data<Arc<RwLock<Answers>>>, players: web::Data<Arc<RwLock<Vec<Player>>>>, active_player: web::Data<Arc<RwLock<ActivePlayer>>>, status: web::Data<Arc<RwLock<Status>>>, server_secret: web::Data<ServerSecret>) -> impl Responder {
 	let ip = match req.peer_addr() {
 		Some(res) => res.ip(),
 		None => return HttpResponse::InternalServerError().body("Could not get IP address".as_bytes())
@@ -275,14 +695,7 @@ async fn get_answer(req: HttpRequest, query: web::Query<AnswerQuery>, pwd: web::
 	
 	let answer = &answers.categories[query.c as usize].answers[query.a as usize];
 	
-	let mut answer_string = match &answer.task {
-		Task::Text(text) => {
-			text.to_string()
-		}
-		Task::Picture(link) => {
-			link.to_string() // TODO!
-		}
-	};
+	let mut answer_string = render_task(&answer.task, query.c, query.a);
 	if answer.double {
 		answer_string = answer_string + " (DOUBLE)";
 	}
@@ -324,12 +737,17 @@ async fn get_answer(req: HttpRequest, query: web::Query<AnswerQuery>, pwd: web::
 	if let Some(value) = &query.value {
 		answers.categories[query.c as usize].answers[query.a as usize].points = *value;
 	}
-	
+
+	// Persist after every scoring action so points are never lost mid-game.
+	let status_code = match *status.read().unwrap() { Status::Registration => 0, Status::BuzzerActive => 1 };
+	let active = active_player.read().unwrap().id;
+	save_snapshot(&pwd, &answers, &players.read().unwrap(), active, status_code, &server_secret.bytes());
+
 	HttpResponse::Ok().body(answer_page.into_bytes())
 }
 
 #[get("/admin")]
-async fn admin(req: HttpRequest, query: web::Query<AdminQuery>, pwd: web::Data<PathBuf>, answers: web::Data<Arc<RwLock<Answers>>>, players: web::Data<Arc<RwLock<Vec<Player>>>>, active_player: web::Data<Arc<RwLock<ActivePlayer>>>, status: web::Data<Arc<RwLock<Status>>>) -> impl Responder {
+async fn admin(req: HttpRequest, query: web::Query<AdminQuery>, pwd: web::Data<PathBuf>, answers: web::Data<Arc<RwLock<Answers>>>, players: web::Data<Arc<RwLock<Vec<Player>>>>, active_player: web::Data<Arc<RwLock<ActivePlayer>>>, status: web::Data<Arc<RwLock<Status>>>, buzzer_state: web::Data<Arc<RwLock<BuzzerState>>>, buzzer_tx: web::Data<broadcast::Sender<String>>, public_address: web::Data<PublicAddress>, server_secret: web::Data<ServerSecret>) -> impl Responder {
 	let ip = match req.peer_addr() {
 		Some(res) => res.ip(),
 		None => return HttpResponse::InternalServerError().body("Could not get IP address".as_bytes())
@@ -337,6 +755,58 @@ async fn admin(req: HttpRequest, query: web::Query<AdminQuery>, pwd: web::Data<P
 	if !ip.is_loopback() {
 		return HttpResponse::Unauthorized().body("Not an admin".as_bytes());
 	}
+	if query.export.unwrap_or(false) {
+		let status_code = match *status.read().unwrap() { Status::Registration => 0, Status::BuzzerActive => 1 };
+		let snapshot = Snapshot {
+			answers: answers.read().unwrap().clone(),
+			players: players.read().unwrap().clone(),
+			active_player: active_player.read().unwrap().id,
+			status: status_code,
+			secret: server_secret.bytes(),
+		};
+		let json = match serde_json::to_vec(&snapshot) {
+			Ok(json) => json,
+			Err(_) => return HttpResponse::InternalServerError().body("Could not serialize game".as_bytes())
+		};
+		return match gzip(&json).await {
+			Ok(archive) => HttpResponse::Ok()
+				.content_type("application/gzip")
+				.insert_header(("content-disposition", "attachment; filename=\"openjeopardy_export.json.gz\""))
+				.body(archive),
+			Err(_) => HttpResponse::InternalServerError().body("Could not compress archive".as_bytes())
+		};
+	}
+	if query.import.unwrap_or(false) {
+		match load_snapshot(&snapshot_path(&pwd)) {
+			Ok(snapshot) => {
+				*answers.write().unwrap() = snapshot.answers;
+				*players.write().unwrap() = snapshot.players;
+				active_player.write().unwrap().id = snapshot.active_player;
+				*status.write().unwrap() = match snapshot.status { 0 => Status::Registration, _ => Status::BuzzerActive };
+				// Restore the signing secret too, so the resumed players' cookies verify.
+				server_secret.set(snapshot.secret);
+			}
+			Err(e) => return HttpResponse::InternalServerError().body(format!("Could not import snapshot: {}", e))
+		}
+	}
+	if query.arm.unwrap_or(false) {
+		let mut buzzer_state = buzzer_state.write().unwrap();
+		buzzer_state.armed = true;
+		buzzer_state.locked = false;
+		buzzer_state.buzzes.clear();
+		let _ = buzzer_tx.send("armed".to_string());
+	}
+	if query.lock.unwrap_or(false) {
+		buzzer_state.write().unwrap().locked = true;
+		let _ = buzzer_tx.send("locked".to_string());
+	}
+	if query.clear.unwrap_or(false) {
+		let mut buzzer_state = buzzer_state.write().unwrap();
+		buzzer_state.armed = false;
+		buzzer_state.locked = false;
+		buzzer_state.buzzes.clear();
+		let _ = buzzer_tx.send("cleared".to_string());
+	}
 	if query.player.is_some() {
 		let mut active_player = active_player.write().unwrap();
 		*active_player = ActivePlayer { id: query.player.unwrap() };
@@ -397,25 +867,61 @@ async fn admin(req: HttpRequest, query: web::Query<AdminQuery>, pwd: web::Data<P
 		Status::BuzzerActive => 0
 	};
 	admin_page = admin_page.replace("STATE", &state.to_string());
-	
+	admin_page = admin_page.replace("QRCODE", &qr_svg(&public_address.0));
+	admin_page = admin_page.replace("BUZZFEED", ADMIN_BUZZ_FEED);
+
 	HttpResponse::Ok().body(admin_page.into_bytes())
 }
 
+#[get("/qr")]
+async fn qr(public_address: web::Data<PublicAddress>) -> impl Responder {
+	let site = format!("{}<div class=\"pad\">{}</div>{}", SITE_HEADER, qr_svg(&public_address.0), SITE_FOOTER).into_bytes();
+	HttpResponse::Ok().body(site)
+}
+
 #[get("/")]
-async fn splash() -> impl Responder {
+async fn splash(public_address: web::Data<PublicAddress>) -> impl Responder {
 	let site = format!("{}<h1 class=\"pad\">Willkommen zum Jeopardy! Gib dir einen Namen und registriere dich!</h1>
 	<form action=\"/register\">
 		<input type=\"text\" id=\"name\" name=\"name\">
 		<input type=\"submit\" class=\"regular\" value=\"Registrieren\">
-	</form>{}", SITE_HEADER, SITE_FOOTER).into_bytes();
+	</form>
+	<div class=\"pad\">{}</div>{}", SITE_HEADER, qr_svg(&public_address.0), SITE_FOOTER).into_bytes();
 	HttpResponse::Ok().body(site)
 }
 
 #[get("/buzzer")]
-async fn buzzer() -> impl Responder {
-	let site = format!("{}<form action=\"/buzz\">
-			<input type=\"submit\" class=\"buzzer\" value=\"Buzzer!\">
-		</form>{}", SITE_HEADER, SITE_FOOTER);
+async fn buzzer(req: HttpRequest, server_secret: web::Data<ServerSecret>) -> impl Responder {
+	// Resolve this client's verified id from its signed session cookie so the page can tell
+	// apart buzz events that concern itself from those of other players. Comparing on the
+	// id (not the display name) keeps two players who picked the same name distinct.
+	let my_id = resolve_player(&req, &server_secret)
+		.map(|id| id.to_string())
+		.unwrap_or_default();
+	let site = format!("{}<button id=\"buzz\" class=\"buzzer\">Buzzer!</button>
+		<h2 id=\"status\" class=\"pad\"></h2>
+		<script>
+			const myId = \"{}\";
+			const status = document.getElementById(\"status\");
+			const button = document.getElementById(\"buzz\");
+			let winner = null;
+			button.onclick = async () => {{
+				const res = await fetch(\"/buzz\");
+				status.textContent = await res.text();
+			}};
+			const ws = new WebSocket((location.protocol === \"https:\" ? \"wss://\" : \"ws://\") + location.host + \"/ws\");
+			ws.onmessage = (e) => {{
+				const parts = e.data.split(\"\\t\");
+				if (parts[0] === \"armed\") {{ winner = null; status.textContent = \"armed\"; }}
+				else if (parts[0] === \"cleared\") {{ winner = null; status.textContent = \"\"; }}
+				else if (parts[0] === \"first\") {{
+					winner = parts[1];
+					status.textContent = (winner === myId) ? \"you buzzed first\" : \"locked out\";
+				}}
+				else if (parts[0] === \"locked\") {{ if (winner !== myId) status.textContent = \"locked out\"; }}
+				else if (parts[0] === \"buzz\") {{ if (winner === null && parts[1] !== myId) status.textContent = \"locked out\"; }}
+			}};
+		</script>{}", SITE_HEADER, my_id, SITE_FOOTER);
 	let site = site.into_bytes();
 	HttpResponse::Ok().body(site)
 }